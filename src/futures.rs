@@ -0,0 +1,126 @@
+// count-write
+// Copyright (C) SOFe
+//
+// Licensed under the Apache License, Version 2.0 (the License);
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an AS IS BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io::{IoSlice, SeekFrom};
+
+use futures::io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite};
+
+use crate::{truncate_vectored, CountRead, CountWrite, Result};
+
+/// Wrapper for `futures::io::AsyncWrite`, used in the `futures` family
+///
+/// *Only available with the `"futures"` feature*
+impl<W: AsyncWrite> AsyncWrite for CountWrite<W> {
+    fn poll_write(self: Pin<&mut Self>, ctx: &mut Context, buf: &[u8]) -> Poll<Result<usize>> {
+        let me = unsafe { self.get_unchecked_mut() };
+        let buf = match me.truncate_to_limit(buf) {
+            Ok(buf) => buf,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        let pin = unsafe { Pin::new_unchecked(&mut me.inner) };
+        let ret = pin.poll_write(ctx, buf);
+        if let Poll::Ready(Ok(written)) = &ret {
+            me.record(*written as u64);
+        }
+        ret
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>, ctx: &mut Context, bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        let me = unsafe { self.get_unchecked_mut() };
+        let remaining = match me.limit_remaining() {
+            Ok(remaining) => remaining,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        let truncated;
+        let bufs = match remaining {
+            Some(remaining) => {
+                truncated = truncate_vectored(bufs, remaining);
+                &truncated[..]
+            }
+            None => bufs,
+        };
+        let pin = unsafe { Pin::new_unchecked(&mut me.inner) };
+        let ret = pin.poll_write_vectored(ctx, bufs);
+        if let Poll::Ready(Ok(written)) = &ret {
+            me.record(*written as u64);
+        }
+        ret
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result> {
+        unsafe { self.map_unchecked_mut(|cw| &mut cw.inner) }.poll_flush(ctx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result> {
+        unsafe { self.map_unchecked_mut(|cw| &mut cw.inner) }.poll_close(ctx)
+    }
+}
+
+/// Wrapper for `futures::io::AsyncSeek`, used in the `futures` family
+///
+/// *Only available with the `"futures"` feature*
+impl<W: AsyncWrite + AsyncSeek> AsyncSeek for CountWrite<W> {
+    fn poll_seek(self: Pin<&mut Self>, ctx: &mut Context, pos: SeekFrom) -> Poll<Result<u64>> {
+        let Self { inner, position, .. } = unsafe { self.get_unchecked_mut() };
+        let pin = unsafe { Pin::new_unchecked(inner) };
+        let ret = pin.poll_seek(ctx, pos);
+        if let Poll::Ready(Ok(new_pos)) = &ret {
+            if let Some(position) = position {
+                position.set(*new_pos);
+            }
+        }
+        ret
+    }
+}
+
+/// Wrapper for `futures::io::AsyncRead`, used in the `futures` family
+///
+/// *Only available with the `"futures"` feature*
+impl<R: AsyncRead> AsyncRead for CountRead<R> {
+    fn poll_read(self: Pin<&mut Self>, ctx: &mut Context, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let Self { inner, count } = unsafe { self.get_unchecked_mut() };
+        let pin = unsafe { Pin::new_unchecked(inner) };
+        let ret = pin.poll_read(ctx, buf);
+        if let Poll::Ready(ret) = &ret {
+            if let Ok(read) = &ret {
+                *count += *read as u64;
+            }
+        }
+        ret
+    }
+}
+
+/// Wrapper for `futures::io::AsyncBufRead`, used in the `futures` family
+///
+/// *Only available with the `"futures"` feature*
+impl<R: AsyncBufRead> AsyncBufRead for CountRead<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<&[u8]>> {
+        unsafe { self.map_unchecked_mut(|cr| &mut cr.inner) }.poll_fill_buf(ctx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let Self { inner, count } = unsafe { self.get_unchecked_mut() };
+        *count += amt as u64;
+        unsafe { Pin::new_unchecked(inner) }.consume(amt)
+    }
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write;
+use std::io::{Error, ErrorKind, IoSlice, Read, Seek, SeekFrom, Write};
 
 type Result<T = (), E = std::io::Error> = std::result::Result<T, E>;
 
@@ -65,12 +65,71 @@ type Result<T = (), E = std::io::Error> = std::result::Result<T, E>;
 /// # Ok::<(), std::io::Error>(())
 /// ```
 ///
+/// [`write_vectored`][write-vectored] is counted by the number of bytes the inner writer reports
+/// as written, not by summing the lengths of the [`IoSlice`]s passed in.
+///
+/// ```
+/// use std::io::{IoSlice, Result, Write};
+///
+/// use count_write::CountWrite;
+///
+/// /// A dummy struct that only accepts the first slice of a vectored write.
+/// struct OnlyFirstSlice;
+///
+/// impl Write for OnlyFirstSlice {
+///     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+///         Ok(buf.len())
+///     }
+///
+///     fn flush(&mut self) -> Result<()> {
+///         Ok(())
+///     }
+///
+///     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+///         Ok(bufs.first().map_or(0, |buf| buf.len()))
+///     }
+/// }
+///
+/// let mut cw = CountWrite::from(OnlyFirstSlice);
+/// let bufs = [IoSlice::new(b"abc"), IoSlice::new(b"defgh")];
+/// cw.write_vectored(&bufs)?;
+/// assert_eq!(cw.count(), 3); // not 8, the combined length of both slices
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
 /// [Write]: https://doc.rust-lang.org/std/io/trait.Write.html
 /// [write_all]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
 /// [write-macro]: https://doc.rust-lang.org/std/macro.write.html
+/// [write-vectored]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_vectored
 pub struct CountWrite<W> {
     inner: W,
     count: u64,
+    position: Option<Position>,
+    observer: Option<Box<dyn FnMut(u64, u64) + Send>>,
+    limit: Option<u64>,
+}
+
+/// Tracks the logical position within the inner writer and the high-water mark it has reached,
+/// so that seeking backwards to patch previously written regions does not inflate [`CountWrite::len`].
+pub(crate) struct Position {
+    pos: u64,
+    max: u64,
+}
+
+impl Position {
+    /// Advances the logical position by `written` bytes, as would happen after a successful
+    /// write, and raises the high-water mark if the new position exceeds it.
+    pub(crate) fn advance(&mut self, written: u64) {
+        self.pos += written;
+        self.max = self.max.max(self.pos);
+    }
+
+    /// Sets the logical position directly, as would happen after a seek. The high-water mark is
+    /// left untouched: seeking past it (e.g. to probe the end of the stream) must not make
+    /// [`CountWrite::len`] claim bytes exist there until they are actually written.
+    pub(crate) fn set(&mut self, pos: u64) {
+        self.pos = pos;
+    }
 }
 
 impl<W> CountWrite<W> {
@@ -79,28 +138,300 @@ impl<W> CountWrite<W> {
         self.count
     }
 
+    /// Returns the size of the file that would be produced by the inner writer, i.e. the
+    /// high-water mark of the logical position reached by writes and seeks.
+    ///
+    /// Only meaningful for writers constructed with [`CountWrite::tracking_position`]; otherwise
+    /// this is equivalent to [`CountWrite::count`].
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.position.as_ref().map_or(self.count, |position| position.max)
+    }
+
     /// Extracts the inner writer, discarding this wrapper
     pub fn into_inner(self) -> W {
         self.inner
     }
+
+    /// Returns the number of bytes that can still be written before the limit set by
+    /// [`CountWrite::with_limit`] is reached, or [`u64::MAX`] if no limit was set.
+    pub fn remaining(&self) -> u64 {
+        self.limit.map_or(u64::MAX, |limit| limit.saturating_sub(self.count))
+    }
+
+    /// Makes this writer track the current logical position within a seekable inner writer (e.g.
+    /// a [`File`][std::fs::File] or a `tokio::fs::File`) and its high-water mark, reported by
+    /// [`CountWrite::len`]; [`CountWrite::count`] remains the running total of bytes written.
+    ///
+    /// This is for tools such as archive encoders that seek backwards to patch headers and then
+    /// continue appending: [`CountWrite::len`] reports the final size of the produced file even
+    /// though writes overlap previously written regions. This method itself just enables the
+    /// tracking and does not require `W` to implement `Seek`/`AsyncSeek` — that bound lives on
+    /// the `Seek`/`AsyncSeek` impls forwarding to the inner writer.
+    ///
+    /// ```
+    /// use std::io::{Cursor, Seek, SeekFrom, Write};
+    ///
+    /// use count_write::CountWrite;
+    ///
+    /// let mut cw = CountWrite::from(Cursor::new(vec![])).tracking_position();
+    /// cw.write_all(b"0123456789")?;
+    /// cw.seek(SeekFrom::Start(0))?;
+    /// cw.write_all(b"ab")?; // patch the header, not extending the file
+    /// cw.seek(SeekFrom::End(0))?;
+    /// cw.write_all(b"xyz")?; // continue appending
+    /// assert_eq!(cw.len(), 13); // the final file size, not the 15 bytes written in total
+    /// assert_eq!(cw.count(), 15);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn tracking_position(mut self) -> Self {
+        self.position = Some(Position { pos: 0, max: 0 });
+        self
+    }
 }
 
 impl<W> From<W> for CountWrite<W> {
     fn from(inner: W) -> Self {
-        Self { inner, count: 0 }
+        Self { inner, count: 0, position: None, observer: None, limit: None }
+    }
+}
+
+impl<W> CountWrite<W> {
+    /// Sets `f` to be invoked with `(delta, total)` after every successful write, where `delta`
+    /// is the number of bytes just written and `total` is the new running
+    /// [`count`][CountWrite::count].
+    ///
+    /// This lets callers wire a progress bar or rate limiter into an existing writer chain
+    /// without polling [`count`][CountWrite::count] themselves.
+    ///
+    /// Chainable with [`CountWrite::tracking_position`] and [`CountWrite::with_limit`] so any
+    /// combination of these features can be constructed, e.g.
+    /// `CountWrite::from(inner).with_observer(f).with_limit(max)`.
+    ///
+    /// `f` must be [`Send`] so that a `CountWrite` with an observer can still be moved into a
+    /// spawned task (e.g. `tokio::spawn`).
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use count_write::CountWrite;
+    ///
+    /// let seen = Arc::new(Mutex::new(vec![]));
+    /// let seen_in_observer = Arc::clone(&seen);
+    /// let mut cw = CountWrite::from(vec![])
+    ///     .with_observer(move |delta, total| seen_in_observer.lock().unwrap().push((delta, total)));
+    /// cw.write_all(b"abc")?;
+    /// cw.write_all(b"de")?;
+    /// assert_eq!(*seen.lock().unwrap(), vec![(3, 3), (2, 5)]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_observer<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(u64, u64) + Send + 'static,
+    {
+        self.observer = Some(Box::new(f));
+        self
+    }
+
+    /// Caps the total number of bytes that may be written through this writer to `max`. Writes
+    /// that would exceed the limit are truncated to the bytes remaining; once no bytes remain,
+    /// writes fail with [`ErrorKind::WriteZero`] instead of delegating to the inner writer.
+    ///
+    /// This is useful for guarding against runaway serializers or for respecting a disk or
+    /// response quota. Chainable with [`CountWrite::tracking_position`] and
+    /// [`CountWrite::with_observer`].
+    ///
+    /// ```
+    /// use std::io::{ErrorKind, IoSlice, Write};
+    ///
+    /// use count_write::CountWrite;
+    ///
+    /// let mut cw = CountWrite::from(vec![]).with_limit(5);
+    /// let bufs = [IoSlice::new(b"abc"), IoSlice::new(b"defgh")];
+    /// let written = cw.write_vectored(&bufs)?; // truncated right at the limit
+    /// assert_eq!(written, 5);
+    /// assert_eq!(cw.count(), 5);
+    ///
+    /// let err = cw.write(b"x").unwrap_err(); // limit already exhausted
+    /// assert_eq!(err.kind(), ErrorKind::WriteZero);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_limit(mut self, max: u64) -> Self {
+        self.limit = Some(max);
+        self
     }
 }
 
 impl<W: Write> Write for CountWrite<W> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let buf = self.truncate_to_limit(buf)?;
         let written = self.inner.write(buf)?;
-        self.count += written as u64;
+        self.record(written as u64);
         Ok(written)
     }
 
     fn flush(&mut self) -> Result {
         self.inner.flush()
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let truncated;
+        let bufs = match self.limit_remaining()? {
+            Some(remaining) => {
+                truncated = truncate_vectored(bufs, remaining);
+                &truncated[..]
+            }
+            None => bufs,
+        };
+        let written = self.inner.write_vectored(bufs)?;
+        self.record(written as u64);
+        Ok(written)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+impl<W> CountWrite<W> {
+    /// Records a successful write of `written` bytes: advances [`CountWrite::count`], the
+    /// logical position (if tracked), and notifies the observer (if set).
+    ///
+    /// The single call site for this bookkeeping shared by the sync and async `Write` impls.
+    fn record(&mut self, written: u64) {
+        self.count += written;
+        if let Some(position) = &mut self.position {
+            position.advance(written);
+        }
+        if let Some(observer) = &mut self.observer {
+            observer(written, self.count);
+        }
+    }
+
+    /// Returns the number of bytes that may still be written under the limit set by
+    /// [`CountWrite::with_limit`] (`None` if no limit was set). Fails with
+    /// [`ErrorKind::WriteZero`] if the limit has already been reached.
+    fn limit_remaining(&self) -> Result<Option<u64>> {
+        match self.limit {
+            Some(limit) => {
+                let remaining = limit.saturating_sub(self.count);
+                if remaining == 0 {
+                    return Err(Error::new(ErrorKind::WriteZero, "CountWrite limit reached"));
+                }
+                Ok(Some(remaining))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Truncates `buf` to the number of bytes remaining under the limit set by
+    /// [`CountWrite::with_limit`], or returns it unchanged if no limit is set. Fails with
+    /// [`ErrorKind::WriteZero`] if the limit has already been reached.
+    fn truncate_to_limit<'a>(&self, buf: &'a [u8]) -> Result<&'a [u8]> {
+        Ok(match self.limit_remaining()? {
+            Some(remaining) => &buf[..(remaining.min(buf.len() as u64) as usize)],
+            None => buf,
+        })
+    }
+}
+
+/// Truncates a list of [`IoSlice`]s to at most `remaining` bytes in total, dropping or
+/// shortening slices as needed so gathering writes cannot exceed a [`CountWrite`] limit.
+pub(crate) fn truncate_vectored<'a>(bufs: &[IoSlice<'a>], mut remaining: u64) -> Vec<IoSlice<'a>> {
+    let mut truncated = Vec::with_capacity(bufs.len());
+    for buf in bufs {
+        if remaining == 0 {
+            break;
+        }
+        let take = remaining.min(buf.len() as u64) as usize;
+        truncated.push(IoSlice::new(&buf[..take]));
+        remaining -= take as u64;
+    }
+    truncated
+}
+
+impl<W: Write + Seek> Seek for CountWrite<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = self.inner.seek(pos)?;
+        if let Some(position) = &mut self.position {
+            position.set(new_pos);
+        }
+        Ok(new_pos)
+    }
+}
+
+/// A wrapper for [`std::io::Read`][Read] (and other optional Read implementations) that counts
+/// the total number of bytes read successfully.
+///
+/// ```
+/// use std::io::Read;
+///
+/// use count_write::CountRead;
+///
+/// let mut cr = CountRead::from("abc".as_bytes());
+/// let mut buf = [0u8; 3];
+/// cr.read(&mut buf)?;
+/// assert_eq!(cr.count(), 3);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// If the inner reader does not fill the whole buffer in one call,
+/// only the bytes actually read are counted.
+///
+/// ```
+/// use std::io::{Read, Result};
+///
+/// use count_write::CountRead;
+///
+/// /// A dummy struct that only ever reads the first two bytes of the buffer it's given.
+/// struct OnlyPrefix;
+///
+/// impl Read for OnlyPrefix {
+///     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+///         let n = buf.len().min(2);
+///         buf[..n].copy_from_slice(&b"ab"[..n]);
+///         Ok(n)
+///     }
+/// }
+///
+/// let mut cr = CountRead::from(OnlyPrefix);
+/// let mut buf = [0u8; 5];
+/// cr.read(&mut buf)?;
+/// assert_eq!(cr.count(), 2); // not 5, the size of the requested buffer
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [Read]: https://doc.rust-lang.org/std/io/trait.Read.html
+pub struct CountRead<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R> CountRead<R> {
+    /// Returns the number of bytes successfully read so far
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Extracts the inner reader, discarding this wrapper
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> From<R> for CountRead<R> {
+    fn from(inner: R) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<R: Read> Read for CountRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
 }
 
 #[cfg(feature = "futures")]
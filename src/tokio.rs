@@ -15,27 +15,58 @@
 
 use core::pin::Pin;
 use core::task::{Context, Poll};
+use std::io::{IoSlice, SeekFrom};
 
-use tokio_io::AsyncWrite;
+use tokio_io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
 
-use crate::{CountWrite, Result};
+use crate::{truncate_vectored, CountRead, CountWrite, Result};
 
 /// Wrapper for `tokio_io::AsyncWrite`, used in the `tokio` family
 ///
 /// *Only available with the `"tokio"` feature*
 impl<W: AsyncWrite> AsyncWrite for CountWrite<W> {
     fn poll_write(self: Pin<&mut Self>, ctx: &mut Context, buf: &[u8]) -> Poll<Result<usize>> {
-        let Self { inner, count } = unsafe { self.get_unchecked_mut() };
-        let pin = unsafe { Pin::new_unchecked(inner) };
+        let me = unsafe { self.get_unchecked_mut() };
+        let buf = match me.truncate_to_limit(buf) {
+            Ok(buf) => buf,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        let pin = unsafe { Pin::new_unchecked(&mut me.inner) };
         let ret = pin.poll_write(ctx, buf);
-        if let Poll::Ready(ret) = &ret {
-            if let Ok(written) = &ret {
-                *count += *written as u64;
+        if let Poll::Ready(Ok(written)) = &ret {
+            me.record(*written as u64);
+        }
+        ret
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>, ctx: &mut Context, bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize>> {
+        let me = unsafe { self.get_unchecked_mut() };
+        let remaining = match me.limit_remaining() {
+            Ok(remaining) => remaining,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        let truncated;
+        let bufs = match remaining {
+            Some(remaining) => {
+                truncated = truncate_vectored(bufs, remaining);
+                &truncated[..]
             }
+            None => bufs,
+        };
+        let pin = unsafe { Pin::new_unchecked(&mut me.inner) };
+        let ret = pin.poll_write_vectored(ctx, bufs);
+        if let Poll::Ready(Ok(written)) = &ret {
+            me.record(*written as u64);
         }
         ret
     }
 
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
     fn poll_flush(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result> {
         unsafe { self.map_unchecked_mut(|cw| &mut cw.inner) }.poll_flush(ctx)
     }
@@ -44,3 +75,57 @@ impl<W: AsyncWrite> AsyncWrite for CountWrite<W> {
         unsafe { self.map_unchecked_mut(|cw| &mut cw.inner) }.poll_shutdown(ctx)
     }
 }
+
+/// Wrapper for `tokio_io::AsyncSeek`, used in the `tokio` family
+///
+/// *Only available with the `"tokio"` feature*
+impl<W: AsyncWrite + AsyncSeek> AsyncSeek for CountWrite<W> {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> Result<()> {
+        unsafe { self.map_unchecked_mut(|cw| &mut cw.inner) }.start_seek(position)
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<u64>> {
+        let Self { inner, position, .. } = unsafe { self.get_unchecked_mut() };
+        let pin = unsafe { Pin::new_unchecked(inner) };
+        let ret = pin.poll_complete(ctx);
+        if let Poll::Ready(Ok(new_pos)) = &ret {
+            if let Some(position) = position {
+                position.set(*new_pos);
+            }
+        }
+        ret
+    }
+}
+
+/// Wrapper for `tokio_io::AsyncRead`, used in the `tokio` family
+///
+/// *Only available with the `"tokio"` feature*
+impl<R: AsyncRead> AsyncRead for CountRead<R> {
+    fn poll_read(
+        self: Pin<&mut Self>, ctx: &mut Context, buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        let Self { inner, count } = unsafe { self.get_unchecked_mut() };
+        let pin = unsafe { Pin::new_unchecked(inner) };
+        let filled_before = buf.filled().len();
+        let ret = pin.poll_read(ctx, buf);
+        if let Poll::Ready(Ok(())) = &ret {
+            *count += (buf.filled().len() - filled_before) as u64;
+        }
+        ret
+    }
+}
+
+/// Wrapper for `tokio_io::AsyncBufRead`, used in the `tokio` family
+///
+/// *Only available with the `"tokio"` feature*
+impl<R: AsyncBufRead> AsyncBufRead for CountRead<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Result<&[u8]>> {
+        unsafe { self.map_unchecked_mut(|cr| &mut cr.inner) }.poll_fill_buf(ctx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let Self { inner, count } = unsafe { self.get_unchecked_mut() };
+        *count += amt as u64;
+        unsafe { Pin::new_unchecked(inner) }.consume(amt)
+    }
+}